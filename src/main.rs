@@ -68,30 +68,35 @@ fn main() {
 // when something is able to call something that might fails it may be better to handle the error from the calling code
 // sending errors from inside the called function to the calling code is known as propagating the error and it gives more control to the calling code
 // information on how to handle the error could be in the calling code
+//
+// the reusable version of this (UsernameError, read_username) now lives in the `error_handling`
+// library crate (src/lib.rs) so it can actually be depended on; these two keep the original
+// hardcoded-path demo shape for comparison
 use std::fs::File;
-use std::io;
 use std::io::Read;
 
+use error_handling::UsernameError;
+
 // returns the result with the variant
-fn read_username_from_file() -> Result<String, io::Error> {
+fn read_username_from_file() -> Result<String, UsernameError> {
     let f = File::open("hello.txt");
 
     let mut f = match f {
         Ok(file) => file,
-        Err(e) => return Err(e),
+        Err(e) => return Err(UsernameError::from(e)),
     };
 
     let mut s = String::new();
 
     match f.read_to_string(&mut s) {
         Ok(_) => Ok(s),
-        Err(e) => Err(e),
+        Err(e) => Err(UsernameError::from(e)),
     }
 }
 
 // returns the result with the variant shortcut method using ? operator
 
-fn read_username_from_file2() -> Result<String, io::Error> {
+fn read_username_from_file2() -> Result<String, UsernameError> {
     // if the result is an ok variant the value inside the Ok will get returned from the expression and the program continues
     let mut f = File::open("hello.txt")?;
     // if the result is an err variant the err will be returned from the whole function
@@ -139,26 +144,5 @@ fn read_username_from_file2() -> Result<String, io::Error> {
 // pub struct Guess {
 //     value: i32, // creates a structure with a value allowed called i32
 // }
-
-// adds an associated function named new on Guess that creates instances of guess values
-// the new function takes one parameter named value of type i32 and returns a guess struct
-// the new functions tests value to make sure it's between 1 and 100 and if it doesn't pass it calls panic!
-// if it does then we create a new guess with its value set to the value parameter and return the Guess
-// next we have a method named value that borrows self
-// this is called a getter because it's purpose is to get some data from its fields and return it
-// this is important because the value field of guess struct is private
-// impl Guess {
-//     pub fn new(value: i32) -> Guess {
-//         if value < 1 || value > 100 {
-//             panic!("Guess value must be between 1 and 100, got {}.", value);
-//         }
-
-//         Guess {
-//             value
-//         }
-//     }
-
-//     pub fn value(&self) -> i32 {
-//         self.value
-//     }
-// }
+//
+// the live version of this (Guess, GuessError) now lives in the `error_handling` library crate